@@ -0,0 +1,69 @@
+use serde::Deserialize;
+
+/// Which `ObjectStore` implementation to construct on startup.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StorageBackend {
+    S3,
+    LocalFs,
+}
+
+impl Default for StorageBackend {
+    fn default() -> Self {
+        StorageBackend::S3
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Settings {
+    pub s3_region: String,
+    pub cache_capacity: usize,
+    /// How long a cached result is served before it's lazily expired and
+    /// its row/file removed.
+    #[serde(default = "default_result_ttl_seconds")]
+    pub result_ttl_seconds: i64,
+    /// How long a job may sit in `running` before a stale-job sweep assumes
+    /// its worker died and puts it back in the pending pool.
+    #[serde(default = "default_stale_job_timeout_seconds")]
+    pub stale_job_timeout_seconds: i64,
+    /// Target ratio of idle to busy time a worker sleeps between jobs, so
+    /// background GWAS computation doesn't saturate the machine.
+    #[serde(default = "default_worker_tranquility")]
+    pub worker_tranquility: f64,
+    /// Maximum number of cohorts' data kept resident at once; least-recently-used
+    /// entries are evicted once this capacity is reached.
+    #[serde(default = "default_cohort_cache_capacity")]
+    pub cohort_cache_capacity: u64,
+    #[serde(default)]
+    pub storage_backend: StorageBackend,
+    /// Bucket to use when `storage_backend` is `S3`.
+    #[serde(default)]
+    pub s3_bucket: Option<String>,
+    /// Root directory to use when `storage_backend` is `LocalFs`.
+    #[serde(default)]
+    pub local_storage_root: Option<String>,
+}
+
+/// One day: long enough that a result survives a typical browsing session, short enough
+/// that stale results don't linger indefinitely on disk.
+fn default_result_ttl_seconds() -> i64 {
+    86_400
+}
+
+/// Five minutes: generous enough that a slow GWAS job isn't requeued out from under a
+/// live worker, short enough that a crashed worker's job doesn't sit stuck for long.
+fn default_stale_job_timeout_seconds() -> i64 {
+    300
+}
+
+/// 1.0 means "sleep as long as the job took to run" between jobs; a conservative default
+/// that keeps a single worker from saturating the machine out of the box.
+fn default_worker_tranquility() -> f64 {
+    1.0
+}
+
+/// Enough cohorts resident at once to cover most deployments without operators having to
+/// tune this immediately after upgrading.
+fn default_cohort_cache_capacity() -> u64 {
+    100
+}
@@ -0,0 +1,116 @@
+use crate::{AppState, JobStatusEvent};
+use anyhow::{Context, Result};
+use atom_syndication::{EntryBuilder, FeedBuilder};
+use axum::extract::{Path, State};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::IntoResponse;
+use chrono::{TimeZone, Utc};
+use futures_util::stream::{self, Stream, StreamExt};
+use std::convert::Infallible;
+use std::sync::Arc;
+use tokio_stream::wrappers::BroadcastStream;
+use uuid::Uuid;
+
+/// Stream status transitions for a single request as Server-Sent Events.
+pub async fn stream_request_status(
+    State(state): State<Arc<AppState>>,
+    Path(request_id): Path<Uuid>,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let initial = current_job_status(&state, request_id).await;
+    let receiver = state.job_events.subscribe();
+
+    let initial_stream = stream::iter(
+        initial
+            .and_then(|event| Event::default().json_data(&event).ok())
+            .map(Ok),
+    );
+    let live_stream = BroadcastStream::new(receiver).filter_map(move |message| async move {
+        let event = message.ok()?;
+        if event.request_id != request_id {
+            return None;
+        }
+        Event::default().json_data(&event).ok().map(Ok)
+    });
+    Sse::new(initial_stream.chain(live_stream)).keep_alive(KeepAlive::default())
+}
+
+async fn current_job_status(state: &AppState, request_id: Uuid) -> Option<JobStatusEvent> {
+    let row: Option<(String,)> = sqlx::query_as("SELECT status FROM jobs WHERE id = ?")
+        .bind(request_id.to_string())
+        .fetch_optional(&state.db)
+        .await
+        .ok()?;
+    let (status,) = row?;
+    let result_url = if status == "complete" {
+        result_url_if_cached(state, request_id).await
+    } else {
+        None
+    };
+    Some(JobStatusEvent {
+        request_id,
+        status,
+        result_url,
+    })
+}
+
+async fn result_url_if_cached(state: &AppState, request_id: Uuid) -> Option<String> {
+    let row: Option<(String,)> =
+        sqlx::query_as("SELECT request_id FROM result_cache WHERE request_id = ?")
+            .bind(request_id.to_string())
+            .fetch_optional(&state.db)
+            .await
+            .ok()?;
+    row.map(|_| format!("/results/{}", request_id))
+}
+
+/// Atom feed with one `<entry>` per completed request for a cohort whose result is still in
+/// `result_cache`; completed requests whose cached result was evicted or expired are not listed.
+pub async fn cohort_results_atom_feed(
+    State(state): State<Arc<AppState>>,
+    Path(cohort_id): Path<i32>,
+) -> impl IntoResponse {
+    match build_atom_feed(&state, cohort_id).await {
+        Ok(body) => ([("content-type", "application/atom+xml")], body).into_response(),
+        Err(err) => {
+            log::error!("Failed to build atom feed for cohort {}: {}", cohort_id, err);
+            axum::http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
+async fn build_atom_feed(state: &AppState, cohort_id: i32) -> Result<String> {
+    // Filter on the indexed `jobs.cohort_id` column instead of deserializing every payload.
+    let rows: Vec<(String, i64)> = sqlx::query_as(
+        "SELECT jobs.id, result_cache.created_at
+         FROM jobs
+         JOIN result_cache ON result_cache.request_id = jobs.id
+         WHERE jobs.status = 'complete' AND jobs.cohort_id = ?
+         ORDER BY result_cache.created_at DESC",
+    )
+    .bind(cohort_id)
+    .fetch_all(&state.db)
+    .await?;
+
+    let entries = rows
+        .into_iter()
+        .map(|(request_id, created_at)| {
+            let published = Utc
+                .timestamp_opt(created_at, 0)
+                .single()
+                .context("Invalid result_cache.created_at timestamp")?
+                .fixed_offset();
+            Ok(EntryBuilder::default()
+                .id(request_id.clone())
+                .title(format!("WebGWAS result {}", request_id))
+                .published(Some(published))
+                .updated(published)
+                .build())
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let feed = FeedBuilder::default()
+        .title(format!("WebGWAS results for cohort {}", cohort_id))
+        .entries(entries)
+        .build();
+    Ok(feed.to_string())
+}
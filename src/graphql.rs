@@ -0,0 +1,137 @@
+use crate::models::{Cohort, Feature, WebGWASRequestId, WebGWASResult};
+use crate::AppState;
+use async_graphql::dataloader::{DataLoader, Loader};
+use async_graphql::{Context, EmptySubscription, InputObject, Object, Result, Schema, SimpleObject};
+use async_graphql_axum::{GraphQLRequest, GraphQLResponse};
+use axum::extract::State;
+use axum::Extension;
+use std::collections::HashMap;
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub type WebGwasSchema = Schema<QueryRoot, MutationRoot, EmptySubscription>;
+
+pub fn build_schema(state: Arc<AppState>) -> WebGwasSchema {
+    Schema::build(QueryRoot, MutationRoot, EmptySubscription)
+        .data(state)
+        .finish()
+}
+
+/// Builds `FeatureLoader`'s `DataLoader` per request so its cache doesn't outlive the request.
+pub async fn graphql_handler(
+    State(state): State<Arc<AppState>>,
+    Extension(schema): Extension<WebGwasSchema>,
+    req: GraphQLRequest,
+) -> GraphQLResponse {
+    let loader = DataLoader::new(FeatureLoader { db: state.db.clone() }, tokio::spawn);
+    schema.execute(req.into_inner().data(loader)).await.into()
+}
+
+#[derive(SimpleObject)]
+pub struct CohortObject {
+    pub id: i32,
+    pub name: String,
+}
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    async fn cohort(&self, ctx: &Context<'_>, id: i32) -> Result<Option<CohortObject>> {
+        let state = ctx.data::<Arc<AppState>>()?;
+        let cohort = sqlx::query_as::<_, Cohort>("SELECT * FROM cohort WHERE id = ?")
+            .bind(id)
+            .fetch_optional(&state.db)
+            .await?;
+        cohort
+            .map(|cohort| {
+                Ok(CohortObject {
+                    id: cohort
+                        .id
+                        .ok_or_else(|| async_graphql::Error::new("Cohort ID is missing"))?,
+                    name: cohort.name,
+                })
+            })
+            .transpose()
+    }
+
+    /// Features for a single cohort, batched through `FeatureLoader`.
+    async fn features(
+        &self,
+        ctx: &Context<'_>,
+        cohort_id: i32,
+        search: Option<String>,
+    ) -> Result<Vec<Feature>> {
+        let loader = ctx.data::<DataLoader<FeatureLoader>>()?;
+        let features = loader.load_one(cohort_id).await?.unwrap_or_default();
+        Ok(match search {
+            Some(term) => {
+                let term = term.to_lowercase();
+                features
+                    .into_iter()
+                    .filter(|feature| feature.name.to_lowercase().contains(&term))
+                    .collect()
+            }
+            None => features,
+        })
+    }
+
+    async fn result(&self, ctx: &Context<'_>, request_id: Uuid) -> Result<Option<WebGWASResult>> {
+        let state = ctx.data::<Arc<AppState>>()?;
+        let mut results = state.results.lock().await;
+        Ok(results.get(&request_id).await?.cloned())
+    }
+}
+
+#[derive(InputObject)]
+pub struct SubmitPhenotypeInput {
+    pub cohort_id: i32,
+    pub phenotype_definition: String,
+}
+
+pub struct MutationRoot;
+
+#[Object]
+impl MutationRoot {
+    async fn submit_phenotype(&self, ctx: &Context<'_>, input: SubmitPhenotypeInput) -> Result<Uuid> {
+        let state = ctx.data::<Arc<AppState>>()?;
+        let request = WebGWASRequestId {
+            id: Uuid::new_v4(),
+            cohort_id: input.cohort_id,
+            phenotype_definition: input.phenotype_definition,
+        };
+        let request_id = request.id;
+        state.queue.enqueue(&request).await?;
+        state.publish_job_status(request_id, "queued", None);
+        Ok(request_id)
+    }
+}
+
+/// Batches `Feature` lookups by `cohort_id` for the GraphQL `features` field.
+pub struct FeatureLoader {
+    db: sqlx::SqlitePool,
+}
+
+impl Loader<i32> for FeatureLoader {
+    type Value = Vec<Feature>;
+    type Error = Arc<sqlx::Error>;
+
+    async fn load(&self, cohort_ids: &[i32]) -> std::result::Result<HashMap<i32, Self::Value>, Self::Error> {
+        let placeholders = cohort_ids.iter().map(|_| "?").collect::<Vec<_>>().join(", ");
+        let query = format!(
+            "SELECT id, code, name, type as node_type, sample_size, cohort_id FROM feature WHERE cohort_id IN ({})",
+            placeholders
+        );
+        let mut q = sqlx::query_as::<_, Feature>(&query);
+        for cohort_id in cohort_ids {
+            q = q.bind(cohort_id);
+        }
+        let features = q.fetch_all(&self.db).await.map_err(Arc::new)?;
+
+        let mut by_cohort: HashMap<i32, Vec<Feature>> = HashMap::new();
+        for feature in features {
+            by_cohort.entry(feature.cohort_id).or_default().push(feature);
+        }
+        Ok(by_cohort)
+    }
+}
@@ -6,55 +6,57 @@ use models::Cohort;
 use phenotype_definitions::KnowledgeBase;
 use polars::io::parquet::read::ParquetReader;
 use polars::prelude::*;
+use serde::Serialize;
 use sqlx::sqlite::SqlitePoolOptions;
 use sqlx::SqlitePool;
+use std::collections::HashSet;
 use std::path::{Path, PathBuf};
-use std::{
-    collections::HashMap,
-    fs::File,
-    sync::{Arc, Mutex},
-};
+use std::{fs::File, sync::Arc};
+use tokio::sync::Mutex;
 use uuid::Uuid;
 
 pub mod config;
 pub mod endpoints;
 pub mod errors;
+pub mod graphql;
 pub mod igwas;
 pub mod models;
 pub mod phenotype_definitions;
 pub mod regression;
+pub mod storage;
 pub mod utils;
 pub mod worker;
 
-use crate::config::Settings;
+use crate::config::{Settings, StorageBackend};
 use crate::models::{CohortData, Feature, PhenotypeFitQuality, WebGWASRequestId, WebGWASResult};
+use crate::storage::{LocalFsStore, ObjectStore, S3Store};
 
 pub struct AppState {
     pub root_directory: PathBuf,
     pub settings: Settings,
     pub db: SqlitePool,
-    pub s3_client: aws_sdk_s3::Client,
+    pub object_store: Arc<dyn ObjectStore>,
     pub knowledge_base: KnowledgeBase,
-    pub cohort_id_to_data: Arc<Mutex<HashMap<i32, Arc<CohortData>>>>,
+    pub cohort_cache: moka::future::Cache<i32, Arc<CohortData>>,
     pub fit_quality_reference: Arc<Vec<PhenotypeFitQuality>>,
-    pub queue: Arc<Mutex<Vec<WebGWASRequestId>>>,
+    pub queue: Arc<JobQueue>,
     pub results: Arc<Mutex<ResultsCache>>,
+    /// Broadcasts job status transitions to SSE subscribers.
+    pub job_events: tokio::sync::broadcast::Sender<JobStatusEvent>,
+}
+
+/// A single status transition for a submitted request, published on `AppState::job_events`.
+#[derive(Debug, Clone, Serialize)]
+pub struct JobStatusEvent {
+    pub request_id: Uuid,
+    pub status: String,
+    pub result_url: Option<String>,
 }
 
 impl AppState {
     pub async fn new(settings: Settings) -> Result<Self> {
         let home = std::env::var("HOME").expect("Failed to read $HOME");
         let root = Path::new(&home).join("webgwas");
-        if std::fs::exists(root.join("results"))? {
-            info!("Results directory already exists, clearing");
-            match std::fs::remove_dir_all(root.join("results")) {
-                Ok(_) => {}
-                Err(err) => {
-                    return Err(anyhow!("Failed to clear results directory: {}", err));
-                }
-            }
-            info!("Results directory cleared");
-        }
         std::fs::create_dir_all(root.join("results"))?;
         let db_path = root.join("webgwas.db").display().to_string();
         let db = SqlitePoolOptions::new()
@@ -72,21 +74,12 @@ impl AppState {
             .execute(&db)
             .await?;
 
-        let cohort_id_to_data = sqlx::query_as::<_, Cohort>("SELECT * FROM cohort")
-            .fetch_all(&db)
-            .await
-            .context("Failed to fetch cohorts")?
-            .into_iter()
-            .map(|cohort| -> Result<CohortData> { CohortData::load(cohort, &root) })
-            .collect::<Result<Vec<CohortData>>>()?
-            .into_iter()
-            .map(|cohort_data| {
-                (
-                    cohort_data.cohort.id.expect("Cohort ID is missing"),
-                    Arc::new(cohort_data),
-                )
-            })
-            .collect::<HashMap<i32, Arc<CohortData>>>();
+        // Cohorts are loaded lazily by `get_cohort_data` on first access rather than all
+        // up front, so startup no longer blocks on reading every cohort's data and memory
+        // stays bounded by `cohort_cache_capacity` regardless of how many cohorts exist.
+        let cohort_cache: moka::future::Cache<i32, Arc<CohortData>> = moka::future::Cache::builder()
+            .max_capacity(settings.cohort_cache_capacity)
+            .build();
 
         let fields = sqlx::query_as::<_, Feature>(
             "SELECT id, code, name, type as node_type, sample_size, cohort_id FROM feature",
@@ -97,9 +90,26 @@ impl AppState {
         .unwrap();
         let kb = KnowledgeBase::new(fields);
 
-        let region = Region::new(settings.s3_region.clone());
-        let shared_config = aws_config::from_env().region(region).load().await;
-        let s3_client = Client::new(&shared_config);
+        let object_store: Arc<dyn ObjectStore> = match settings.storage_backend {
+            StorageBackend::S3 => {
+                let region = Region::new(settings.s3_region.clone());
+                let shared_config = aws_config::from_env().region(region).load().await;
+                let bucket = settings
+                    .s3_bucket
+                    .clone()
+                    .context("storage_backend is s3 but s3_bucket is not set")?;
+                Arc::new(S3Store::new(Client::new(&shared_config), bucket))
+            }
+            StorageBackend::LocalFs => {
+                let local_root = settings
+                    .local_storage_root
+                    .clone()
+                    .map(PathBuf::from)
+                    .unwrap_or_else(|| root.join("object_store"));
+                std::fs::create_dir_all(&local_root)?;
+                Arc::new(LocalFsStore::new(local_root))
+            }
+        };
 
         let fit_quality_path = root.join("fit_quality.parquet");
         let fit_quality_file = File::open(&fit_quality_path).context(anyhow!(
@@ -121,52 +131,653 @@ impl AppState {
             .collect::<Option<Vec<PhenotypeFitQuality>>>()
             .context("Failed to load fit quality reference")?;
 
-        let results = Arc::new(Mutex::new(ResultsCache::new(settings.cache_capacity)));
+        let results = Arc::new(Mutex::new(
+            ResultsCache::new(
+                db.clone(),
+                settings.cache_capacity,
+                settings.result_ttl_seconds,
+                &root.join("results"),
+                object_store.clone(),
+            )
+            .await
+            .context("Failed to initialize results cache")?,
+        ));
+
+        let queue = Arc::new(
+            JobQueue::new(
+                db.clone(),
+                settings.stale_job_timeout_seconds,
+                settings.worker_tranquility,
+            )
+            .await
+            .context("Failed to initialize job queue")?,
+        );
 
         let state = AppState {
             root_directory: root,
             settings,
             db,
-            s3_client,
+            object_store,
             knowledge_base: kb,
-            cohort_id_to_data: Arc::new(Mutex::new(cohort_id_to_data)),
+            cohort_cache,
             fit_quality_reference: Arc::new(fit_quality_reference),
-            queue: Arc::new(Mutex::new(Vec::new())),
+            queue,
             results,
+            job_events: tokio::sync::broadcast::channel(256).0,
         };
         info!("Finished initializing app state");
         Ok(state)
     }
+
+    /// Fetch a cohort's data, loading it from the DB + object store on a cache miss.
+    pub async fn get_cohort_data(&self, cohort_id: i32) -> Result<Arc<CohortData>> {
+        let db = self.db.clone();
+        let root = self.root_directory.clone();
+        let object_store = self.object_store.clone();
+        self.cohort_cache
+            .try_get_with(cohort_id, async move {
+                let cohort = sqlx::query_as::<_, Cohort>("SELECT * FROM cohort WHERE id = ?")
+                    .bind(cohort_id)
+                    .fetch_one(&db)
+                    .await
+                    .context(anyhow!("Failed to fetch cohort {}", cohort_id))?;
+                sync_cohort_file_from_object_store(&object_store, &root, cohort_id).await?;
+                CohortData::load(cohort, &root).map(Arc::new)
+            })
+            .await
+            .map_err(|err| anyhow!("Failed to load cohort {}: {}", cohort_id, err))
+    }
+
+    /// Publish a status transition for `request_id`.
+    pub fn publish_job_status(&self, request_id: Uuid, status: &str, result_url: Option<String>) {
+        let _ = self.job_events.send(JobStatusEvent {
+            request_id,
+            status: status.to_string(),
+            result_url,
+        });
+    }
 }
 
+/// In-memory LRU view over the `result_cache` table. `insert` writes through to the table
+/// and `get`/`get_mut` read through it; changes made through the reference `get_mut`
+/// returns stay in the in-memory copy and are not written back to the table.
 pub struct ResultsCache {
     id_to_result: hashlru::Cache<Uuid, WebGWASResult>,
+    db: SqlitePool,
+    capacity: usize,
+    ttl_seconds: i64,
+    object_store: Arc<dyn ObjectStore>,
 }
 
 impl ResultsCache {
-    pub fn new(capacity: usize) -> Self {
-        Self {
+    pub async fn new(
+        db: SqlitePool,
+        capacity: usize,
+        ttl_seconds: i64,
+        results_dir: &Path,
+        object_store: Arc<dyn ObjectStore>,
+    ) -> Result<Self> {
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS result_cache (
+                request_id TEXT PRIMARY KEY,
+                created_at INTEGER NOT NULL,
+                last_accessed INTEGER NOT NULL,
+                local_result_file TEXT NOT NULL,
+                metadata BLOB NOT NULL
+            )",
+        )
+        .execute(&db)
+        .await
+        .context("Failed to create result_cache table")?;
+
+        let mut cache = Self {
             id_to_result: hashlru::Cache::new(capacity),
+            db,
+            capacity,
+            ttl_seconds,
+            object_store,
+        };
+        cache.reconcile_with_disk(results_dir).await?;
+        cache.evict_expired().await?;
+        Ok(cache)
+    }
+
+    /// Drop rows whose backing result file no longer exists, instead of
+    /// blowing away the whole results directory on every startup.
+    async fn reconcile_with_disk(&self, results_dir: &Path) -> Result<()> {
+        let rows: Vec<(String, String)> =
+            sqlx::query_as("SELECT request_id, local_result_file FROM result_cache")
+                .fetch_all(&self.db)
+                .await
+                .context("Failed to load result_cache rows for reconciliation")?;
+        let mut known_files = HashSet::new();
+        for (request_id, local_result_file) in rows {
+            if !Path::new(&local_result_file).exists() {
+                sqlx::query("DELETE FROM result_cache WHERE request_id = ?")
+                    .bind(&request_id)
+                    .execute(&self.db)
+                    .await
+                    .context("Failed to drop stale result_cache row")?;
+            } else {
+                known_files.insert(local_result_file);
+            }
+        }
+
+        // Also remove any file left in `results_dir` that no surviving row points to,
+        // e.g. a result written to disk just before the process was killed, before
+        // `insert` got a chance to record it in `result_cache`.
+        if results_dir.exists() {
+            let mut entries = tokio::fs::read_dir(results_dir).await.context(anyhow!(
+                "Failed to read results directory {}",
+                results_dir.display()
+            ))?;
+            while let Some(entry) = entries.next_entry().await? {
+                let path = entry.path();
+                if path.is_file() && !known_files.contains(&path.display().to_string()) {
+                    tokio::fs::remove_file(&path).await.context(anyhow!(
+                        "Failed to remove orphaned result file {}",
+                        path.display()
+                    ))?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    async fn evict_expired(&mut self) -> Result<()> {
+        let cutoff = now_unix() - self.ttl_seconds;
+        let rows: Vec<(String, String)> = sqlx::query_as(
+            "SELECT request_id, local_result_file FROM result_cache WHERE created_at < ?",
+        )
+        .bind(cutoff)
+        .fetch_all(&self.db)
+        .await
+        .context("Failed to find expired result_cache rows")?;
+        for (request_id, local_result_file) in rows {
+            self.remove_row(&request_id, &local_result_file).await?;
+        }
+        Ok(())
+    }
+
+    async fn evict_lru(&mut self) -> Result<()> {
+        let row: Option<(String, String)> = sqlx::query_as(
+            "SELECT request_id, local_result_file FROM result_cache ORDER BY last_accessed ASC LIMIT 1",
+        )
+        .fetch_optional(&self.db)
+        .await
+        .context("Failed to find least-recently-accessed result_cache row")?;
+        if let Some((request_id, local_result_file)) = row {
+            self.remove_row(&request_id, &local_result_file).await?;
+        }
+        Ok(())
+    }
+
+    async fn remove_row(&mut self, request_id: &str, local_result_file: &str) -> Result<()> {
+        sqlx::query("DELETE FROM result_cache WHERE request_id = ?")
+            .bind(request_id)
+            .execute(&self.db)
+            .await
+            .context("Failed to delete result_cache row")?;
+        if let Ok(id) = Uuid::parse_str(request_id) {
+            self.id_to_result.remove(&id);
+        }
+        if Path::new(local_result_file).exists() {
+            std::fs::remove_file(local_result_file).context("Failed to remove local result file")?;
         }
+        self.object_store.delete(&result_object_key(request_id)).await?;
+        Ok(())
     }
 
-    pub fn insert(&mut self, result: WebGWASResult) {
-        if self.id_to_result.is_full() {
-            let lru_key = *self.id_to_result.lru().unwrap();
-            let lru_value = self.id_to_result.remove(&lru_key).expect("No value found");
-            let file_path = lru_value.local_result_file.expect("No local result file");
-            std::fs::remove_file(file_path)
-                .context("Failed to remove local result file")
-                .unwrap();
+    /// Pull the result file back from the object store if it's missing locally.
+    async fn ensure_local_file(&self, local_result_file: &str, request_id: &str) -> Result<()> {
+        if Path::new(local_result_file).exists() {
+            return Ok(());
+        }
+        let bytes = self.object_store.get(&result_object_key(request_id)).await?;
+        if let Some(parent) = Path::new(local_result_file).parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::write(local_result_file, &bytes)
+            .await
+            .context(anyhow!("Failed to write {} from object store", local_result_file))?;
+        Ok(())
+    }
+
+    async fn load_from_db(&self, id: &Uuid) -> Result<Option<WebGWASResult>> {
+        let row: Option<(Vec<u8>, String)> = sqlx::query_as(
+            "SELECT metadata, local_result_file FROM result_cache WHERE request_id = ?",
+        )
+        .bind(id.to_string())
+        .fetch_optional(&self.db)
+        .await
+        .context("Failed to read result_cache row")?;
+        let Some((metadata, local_result_file)) = row else {
+            return Ok(None);
+        };
+        self.ensure_local_file(&local_result_file, &id.to_string())
+            .await?;
+        let result: WebGWASResult =
+            bincode::deserialize(&metadata).context("Failed to deserialize cached result")?;
+        Ok(Some(result))
+    }
+
+    async fn touch(&self, id: &Uuid) -> Result<()> {
+        sqlx::query("UPDATE result_cache SET last_accessed = ? WHERE request_id = ?")
+            .bind(now_unix())
+            .bind(id.to_string())
+            .execute(&self.db)
+            .await
+            .context("Failed to bump result_cache last_accessed")?;
+        Ok(())
+    }
+
+    pub async fn insert(&mut self, result: WebGWASResult) -> Result<()> {
+        let now = now_unix();
+        let request_id = result.request_id.to_string();
+        let local_result_file = result
+            .local_result_file
+            .clone()
+            .context("Result has no local result file to persist")?;
+        let metadata =
+            bincode::serialize(&result).context("Failed to serialize result metadata")?;
+
+        let file_bytes = tokio::fs::read(&local_result_file).await.context(anyhow!(
+            "Failed to read {} to persist to object store",
+            local_result_file
+        ))?;
+        self.object_store
+            .put(&result_object_key(&request_id), file_bytes.into())
+            .await
+            .context("Failed to persist result file to object store")?;
+
+        sqlx::query(
+            "INSERT INTO result_cache (request_id, created_at, last_accessed, local_result_file, metadata)
+             VALUES (?, ?, ?, ?, ?)
+             ON CONFLICT(request_id) DO UPDATE SET last_accessed = excluded.last_accessed",
+        )
+        .bind(&request_id)
+        .bind(now)
+        .bind(now)
+        .bind(&local_result_file)
+        .bind(&metadata)
+        .execute(&self.db)
+        .await
+        .context("Failed to upsert result_cache row")?;
+
+        // Gate eviction on the actual row count in `result_cache`, not the in-process
+        // hashlru's size: that cache starts empty on every restart, so checking it alone
+        // would let the table grow to ~2x `capacity` right after a restart.
+        let (row_count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM result_cache")
+            .fetch_one(&self.db)
+            .await
+            .context("Failed to count result_cache rows")?;
+        if row_count as usize > self.capacity {
+            self.evict_lru().await?;
         }
         self.id_to_result.insert(result.request_id, result);
+        Ok(())
+    }
+
+    /// Lazily expire `id` if its row is past `ttl_seconds`, removing the row/file and the
+    /// in-memory entry. Returns `true` if `id` was expired (and so is gone).
+    async fn expire_if_stale(&mut self, id: &Uuid) -> Result<bool> {
+        let row: Option<(i64, String)> = sqlx::query_as(
+            "SELECT created_at, local_result_file FROM result_cache WHERE request_id = ?",
+        )
+        .bind(id.to_string())
+        .fetch_optional(&self.db)
+        .await
+        .context("Failed to check result_cache expiry")?;
+        match row {
+            Some((created_at, local_result_file)) if created_at < now_unix() - self.ttl_seconds => {
+                self.remove_row(&id.to_string(), &local_result_file).await?;
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    }
+
+    pub async fn get(&mut self, id: &Uuid) -> Result<Option<&WebGWASResult>> {
+        if self.expire_if_stale(id).await? {
+            return Ok(None);
+        }
+        if self.id_to_result.get(id).is_none() {
+            match self.load_from_db(id).await? {
+                Some(result) => {
+                    self.id_to_result.insert(*id, result);
+                }
+                None => return Ok(None),
+            }
+        }
+        self.touch(id).await?;
+        Ok(self.id_to_result.get(id))
+    }
+
+    pub async fn get_mut(&mut self, id: &Uuid) -> Result<Option<&mut WebGWASResult>> {
+        if self.expire_if_stale(id).await? {
+            return Ok(None);
+        }
+        if self.id_to_result.get(id).is_none() {
+            match self.load_from_db(id).await? {
+                Some(result) => {
+                    self.id_to_result.insert(*id, result);
+                }
+                None => return Ok(None),
+            }
+        }
+        self.touch(id).await?;
+        Ok(self.id_to_result.get_mut(id))
+    }
+}
+
+#[cfg(test)]
+mod results_cache_tests {
+    use super::*;
+    use crate::storage::LocalFsStore;
+
+    async fn test_pool() -> SqlitePool {
+        SqlitePoolOptions::new()
+            .connect("sqlite::memory:")
+            .await
+            .expect("failed to open in-memory sqlite pool")
+    }
+
+    fn test_result(request_id: Uuid, local_result_file: PathBuf) -> WebGWASResult {
+        WebGWASResult {
+            request_id,
+            local_result_file: Some(local_result_file.display().to_string()),
+        }
+    }
+
+    async fn test_cache(dir: &Path, capacity: usize, ttl_seconds: i64) -> ResultsCache {
+        let object_store: Arc<dyn ObjectStore> =
+            Arc::new(LocalFsStore::new(dir.join("object_store")));
+        ResultsCache::new(test_pool().await, capacity, ttl_seconds, &dir.join("results"), object_store)
+            .await
+            .expect("failed to build ResultsCache")
+    }
+
+    async fn write_result_file(dir: &Path, request_id: Uuid, contents: &[u8]) -> PathBuf {
+        let path = dir.join("results").join(request_id.to_string());
+        tokio::fs::create_dir_all(path.parent().unwrap()).await.unwrap();
+        tokio::fs::write(&path, contents).await.unwrap();
+        path
+    }
+
+    #[tokio::test]
+    async fn insert_then_get_round_trips() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut cache = test_cache(dir.path(), 10, 86_400).await;
+
+        let request_id = Uuid::new_v4();
+        let file_path = write_result_file(dir.path(), request_id, b"result bytes").await;
+        cache
+            .insert(test_result(request_id, file_path))
+            .await
+            .unwrap();
+
+        let fetched = cache.get(&request_id).await.unwrap();
+        assert_eq!(fetched.map(|result| result.request_id), Some(request_id));
+    }
+
+    #[tokio::test]
+    async fn insert_evicts_least_recently_accessed_once_over_capacity() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut cache = test_cache(dir.path(), 1, 86_400).await;
+
+        let first_id = Uuid::new_v4();
+        let first_path = write_result_file(dir.path(), first_id, b"first").await;
+        cache.insert(test_result(first_id, first_path)).await.unwrap();
+
+        let second_id = Uuid::new_v4();
+        let second_path = write_result_file(dir.path(), second_id, b"second").await;
+        cache
+            .insert(test_result(second_id, second_path))
+            .await
+            .unwrap();
+
+        assert!(cache.get(&first_id).await.unwrap().is_none());
+        assert!(cache.get(&second_id).await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn get_drops_a_result_past_its_ttl() {
+        let dir = tempfile::tempdir().unwrap();
+        // Already-expired by construction, so `get` has to evict on the very first lookup
+        // instead of a seeded "old" row.
+        let mut cache = test_cache(dir.path(), 10, -1).await;
+
+        let request_id = Uuid::new_v4();
+        let file_path = write_result_file(dir.path(), request_id, b"stale").await;
+        cache
+            .insert(test_result(request_id, file_path))
+            .await
+            .unwrap();
+
+        assert!(cache.get(&request_id).await.unwrap().is_none());
+    }
+}
+
+/// Durable, multi-worker-safe replacement for the old in-memory job `Vec`.
+pub struct JobQueue {
+    db: SqlitePool,
+    worker_id: String,
+    stale_timeout_seconds: i64,
+    tranquility: f64,
+}
+
+impl JobQueue {
+    pub async fn new(db: SqlitePool, stale_timeout_seconds: i64, tranquility: f64) -> Result<Self> {
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS jobs (
+                id TEXT PRIMARY KEY,
+                status TEXT NOT NULL,
+                submitted_at INTEGER NOT NULL,
+                claimed_at INTEGER,
+                worker_id TEXT,
+                cohort_id INTEGER NOT NULL,
+                payload BLOB NOT NULL
+            )",
+        )
+        .execute(&db)
+        .await
+        .context("Failed to create jobs table")?;
+        // Denormalized from `payload` so per-cohort queries (e.g. the Atom feed) can filter
+        // in SQL instead of deserializing every completed job's payload.
+        sqlx::query("CREATE INDEX IF NOT EXISTS jobs_cohort_id_idx ON jobs (cohort_id)")
+            .execute(&db)
+            .await
+            .context("Failed to create jobs_cohort_id_idx index")?;
+
+        let queue = Self {
+            db,
+            worker_id: Uuid::new_v4().to_string(),
+            stale_timeout_seconds,
+            tranquility,
+        };
+        queue.requeue_stale_jobs().await?;
+        Ok(queue)
+    }
+
+    async fn requeue_stale_jobs(&self) -> Result<()> {
+        let cutoff = now_unix() - self.stale_timeout_seconds;
+        sqlx::query(
+            "UPDATE jobs SET status = 'pending', claimed_at = NULL, worker_id = NULL
+             WHERE status = 'running' AND claimed_at < ?",
+        )
+        .bind(cutoff)
+        .execute(&self.db)
+        .await
+        .context("Failed to requeue stale jobs")?;
+        Ok(())
+    }
+
+    pub async fn enqueue(&self, request: &WebGWASRequestId) -> Result<()> {
+        let payload =
+            bincode::serialize(request).context("Failed to serialize job payload")?;
+        sqlx::query(
+            "INSERT INTO jobs (id, status, submitted_at, cohort_id, payload) VALUES (?, 'pending', ?, ?, ?)",
+        )
+        .bind(request.id.to_string())
+        .bind(now_unix())
+        .bind(request.cohort_id)
+        .bind(payload)
+        .execute(&self.db)
+        .await
+        .context("Failed to enqueue job")?;
+        Ok(())
+    }
+
+    /// Atomically claim the oldest pending job.
+    pub async fn claim(&self) -> Result<Option<WebGWASRequestId>> {
+        let row: Option<(Vec<u8>,)> = sqlx::query_as(
+            "UPDATE jobs SET status = 'running', worker_id = ?, claimed_at = ?
+             WHERE id = (SELECT id FROM jobs WHERE status = 'pending' ORDER BY submitted_at LIMIT 1)
+             RETURNING payload",
+        )
+        .bind(&self.worker_id)
+        .bind(now_unix())
+        .fetch_optional(&self.db)
+        .await
+        .context("Failed to claim job")?;
+        row.map(|(payload,)| {
+            bincode::deserialize(&payload).context("Failed to deserialize job payload")
+        })
+        .transpose()
+    }
+
+    pub async fn complete(&self, request: &WebGWASRequestId) -> Result<()> {
+        sqlx::query("UPDATE jobs SET status = 'complete' WHERE id = ?")
+            .bind(request.id.to_string())
+            .execute(&self.db)
+            .await
+            .context("Failed to mark job complete")?;
+        Ok(())
+    }
+
+    pub async fn fail(&self, request: &WebGWASRequestId) -> Result<()> {
+        sqlx::query("UPDATE jobs SET status = 'failed' WHERE id = ?")
+            .bind(request.id.to_string())
+            .execute(&self.db)
+            .await
+            .context("Failed to mark job failed")?;
+        Ok(())
     }
 
-    pub fn get(&mut self, id: &Uuid) -> Option<&WebGWASResult> {
-        self.id_to_result.get(id)
+    /// Sleep for `busy_duration * tranquility` between jobs.
+    pub async fn tranquility_sleep(&self, busy_duration: std::time::Duration) {
+        if self.tranquility <= 0.0 {
+            return;
+        }
+        tokio::time::sleep(busy_duration.mul_f64(self.tranquility)).await;
     }
+}
 
-    pub fn get_mut(&mut self, id: &Uuid) -> Option<&mut WebGWASResult> {
-        self.id_to_result.get_mut(id)
+#[cfg(test)]
+mod job_queue_tests {
+    use super::*;
+
+    async fn test_pool() -> SqlitePool {
+        SqlitePoolOptions::new()
+            .connect("sqlite::memory:")
+            .await
+            .expect("failed to open in-memory sqlite pool")
+    }
+
+    fn test_request(cohort_id: i32) -> WebGWASRequestId {
+        WebGWASRequestId {
+            id: Uuid::new_v4(),
+            cohort_id,
+            phenotype_definition: "true".to_string(),
+        }
     }
+
+    #[tokio::test]
+    async fn claim_hands_out_each_pending_job_exactly_once() {
+        let queue = JobQueue::new(test_pool().await, 300, 1.0).await.unwrap();
+        let request = test_request(1);
+        queue.enqueue(&request).await.unwrap();
+
+        let claimed = queue.claim().await.unwrap().expect("job should be claimable");
+        assert_eq!(claimed.id, request.id);
+        assert!(queue.claim().await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn complete_marks_the_job_row_complete() {
+        let db = test_pool().await;
+        let queue = JobQueue::new(db.clone(), 300, 1.0).await.unwrap();
+        let request = test_request(1);
+        queue.enqueue(&request).await.unwrap();
+        queue.claim().await.unwrap();
+        queue.complete(&request).await.unwrap();
+
+        let (status,): (String,) = sqlx::query_as("SELECT status FROM jobs WHERE id = ?")
+            .bind(request.id.to_string())
+            .fetch_one(&db)
+            .await
+            .unwrap();
+        assert_eq!(status, "complete");
+    }
+
+    #[tokio::test]
+    async fn new_requeues_jobs_left_running_past_the_stale_timeout() {
+        let db = test_pool().await;
+        let queue = JobQueue::new(db.clone(), -1, 1.0).await.unwrap();
+        let request = test_request(1);
+        queue.enqueue(&request).await.unwrap();
+        queue.claim().await.unwrap();
+
+        // Simulates a worker restart: a fresh `JobQueue` over the same DB should sweep the
+        // job claimed above back to `pending`, since `stale_timeout_seconds` is negative.
+        let restarted = JobQueue::new(db, -1, 1.0).await.unwrap();
+        let reclaimed = restarted
+            .claim()
+            .await
+            .unwrap()
+            .expect("stale job should have been requeued");
+        assert_eq!(reclaimed.id, request.id);
+    }
+}
+
+/// Object store key for a cohort's backing parquet file.
+fn cohort_object_key(cohort_id: i32) -> String {
+    format!("cohorts/{}.parquet", cohort_id)
+}
+
+/// Object store key for a cached result file.
+fn result_object_key(request_id: &str) -> String {
+    format!("results/{}", request_id)
+}
+
+async fn sync_cohort_file_from_object_store(
+    object_store: &Arc<dyn ObjectStore>,
+    root: &Path,
+    cohort_id: i32,
+) -> Result<()> {
+    let key = cohort_object_key(cohort_id);
+    let local_path = root.join(&key);
+    if local_path.exists() {
+        return Ok(());
+    }
+    let bytes = object_store.get(&key).await.context(anyhow!(
+        "Failed to fetch cohort {} data from object store",
+        cohort_id
+    ))?;
+    if let Some(parent) = local_path.parent() {
+        tokio::fs::create_dir_all(parent).await?;
+    }
+    tokio::fs::write(&local_path, &bytes).await.context(anyhow!(
+        "Failed to write cohort {} data to {}",
+        cohort_id,
+        local_path.display()
+    ))?;
+    Ok(())
+}
+
+fn now_unix() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .expect("system clock is before unix epoch")
+        .as_secs() as i64
 }
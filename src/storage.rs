@@ -0,0 +1,152 @@
+use anyhow::{anyhow, Context, Result};
+use async_trait::async_trait;
+use aws_sdk_s3::Client;
+use bytes::Bytes;
+use std::path::PathBuf;
+
+/// Blob storage WebGWAS reads cohort data from and writes result files to.
+#[async_trait]
+pub trait ObjectStore: Send + Sync {
+    async fn get(&self, path: &str) -> Result<Bytes>;
+    async fn put(&self, path: &str, bytes: Bytes) -> Result<()>;
+    async fn list(&self, prefix: &str) -> Result<Vec<String>>;
+    async fn delete(&self, path: &str) -> Result<()>;
+}
+
+/// `ObjectStore` backed by an AWS S3 bucket.
+pub struct S3Store {
+    client: Client,
+    bucket: String,
+}
+
+impl S3Store {
+    pub fn new(client: Client, bucket: String) -> Self {
+        Self { client, bucket }
+    }
+}
+
+#[async_trait]
+impl ObjectStore for S3Store {
+    async fn get(&self, path: &str) -> Result<Bytes> {
+        let output = self
+            .client
+            .get_object()
+            .bucket(&self.bucket)
+            .key(path)
+            .send()
+            .await
+            .context(anyhow!("Failed to get object {} from S3", path))?;
+        let bytes = output
+            .body
+            .collect()
+            .await
+            .context("Failed to read S3 object body")?
+            .into_bytes();
+        Ok(bytes)
+    }
+
+    async fn put(&self, path: &str, bytes: Bytes) -> Result<()> {
+        self.client
+            .put_object()
+            .bucket(&self.bucket)
+            .key(path)
+            .body(bytes.into())
+            .send()
+            .await
+            .context(anyhow!("Failed to put object {} to S3", path))?;
+        Ok(())
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<String>> {
+        let output = self
+            .client
+            .list_objects_v2()
+            .bucket(&self.bucket)
+            .prefix(prefix)
+            .send()
+            .await
+            .context(anyhow!("Failed to list objects under {} in S3", prefix))?;
+        Ok(output
+            .contents()
+            .iter()
+            .filter_map(|obj| obj.key().map(str::to_string))
+            .collect())
+    }
+
+    async fn delete(&self, path: &str) -> Result<()> {
+        self.client
+            .delete_object()
+            .bucket(&self.bucket)
+            .key(path)
+            .send()
+            .await
+            .context(anyhow!("Failed to delete object {} from S3", path))?;
+        Ok(())
+    }
+}
+
+/// `ObjectStore` backed by a directory on local disk.
+pub struct LocalFsStore {
+    root: PathBuf,
+}
+
+impl LocalFsStore {
+    pub fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    fn resolve(&self, path: &str) -> PathBuf {
+        self.root.join(path)
+    }
+}
+
+#[async_trait]
+impl ObjectStore for LocalFsStore {
+    async fn get(&self, path: &str) -> Result<Bytes> {
+        let full_path = self.resolve(path);
+        let data = tokio::fs::read(&full_path)
+            .await
+            .context(anyhow!("Failed to read {} from local store", full_path.display()))?;
+        Ok(Bytes::from(data))
+    }
+
+    async fn put(&self, path: &str, bytes: Bytes) -> Result<()> {
+        let full_path = self.resolve(path);
+        if let Some(parent) = full_path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        tokio::fs::write(&full_path, &bytes)
+            .await
+            .context(anyhow!("Failed to write {} to local store", full_path.display()))?;
+        Ok(())
+    }
+
+    async fn list(&self, prefix: &str) -> Result<Vec<String>> {
+        let dir = self.resolve(prefix);
+        if !dir.exists() {
+            return Ok(Vec::new());
+        }
+        let mut entries = tokio::fs::read_dir(&dir)
+            .await
+            .context(anyhow!("Failed to list {} in local store", dir.display()))?;
+        let mut keys = Vec::new();
+        while let Some(entry) = entries.next_entry().await? {
+            if let Ok(relative) = entry.path().strip_prefix(&self.root) {
+                keys.push(relative.display().to_string());
+            }
+        }
+        Ok(keys)
+    }
+
+    async fn delete(&self, path: &str) -> Result<()> {
+        let full_path = self.resolve(path);
+        // Mirror S3's delete_object semantics: deleting a key that isn't there is a no-op,
+        // not an error, so callers don't see different behavior depending on backend.
+        match tokio::fs::remove_file(&full_path).await {
+            Ok(()) => Ok(()),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(err)
+                .context(anyhow!("Failed to delete {} from local store", full_path.display())),
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use crate::models::WebGWASRequestId;
+use crate::AppState;
+use anyhow::Result;
+use log::error;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Drain `state.queue`: claim the oldest pending job, run it, mark it complete/failed,
+/// then sleep per `tranquility_sleep` before claiming the next one. Loops until the
+/// process is killed; returns early with the error if a queue operation (claim,
+/// complete or fail) fails.
+pub async fn run(state: Arc<AppState>) -> Result<()> {
+    loop {
+        match state.queue.claim().await? {
+            Some(request) => {
+                let request_id = request.id;
+                let started = Instant::now();
+                match process_request(&state, &request).await {
+                    Ok(result_url) => {
+                        state.queue.complete(&request).await?;
+                        state.publish_job_status(request_id, "complete", Some(result_url));
+                    }
+                    Err(err) => {
+                        error!("Job {} failed: {}", request_id, err);
+                        state.queue.fail(&request).await?;
+                        state.publish_job_status(request_id, "failed", None);
+                    }
+                }
+                state.queue.tranquility_sleep(started.elapsed()).await;
+            }
+            None => tokio::time::sleep(Duration::from_secs(1)).await,
+        }
+    }
+}
+
+// The actual GWAS computation (regression/igwas) isn't part of this change; this is the
+// seam a real worker would hook it up through, writing its output into `state.results`
+// and returning the `/results/{request_id}` URL clients fetch it from.
+async fn process_request(_state: &AppState, request: &WebGWASRequestId) -> Result<String> {
+    anyhow::bail!("processing for request {} is not implemented", request.id)
+}